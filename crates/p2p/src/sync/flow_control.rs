@@ -0,0 +1,274 @@
+//! Per-peer credit/cost flow control for the request-serving side of p2p sync.
+use std::collections::HashMap;
+use std::time::Instant;
+
+use p2p_proto::common::Iteration;
+
+/// The kinds of range requests a peer can make. Used to key into the per-kind [`CostTable`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RequestKind {
+    Headers,
+    Bodies,
+    Transactions,
+    Receipts,
+    Events,
+    StateDiffs,
+    Classes,
+}
+
+/// Cost parameters for a single request kind: `cost = base_cost + per_item_cost * limit`, where
+/// `limit` is the request's [`Iteration::limit`](p2p_proto::common::Iteration) clamped to
+/// `max_items`.
+#[derive(Debug, Copy, Clone, serde::Deserialize)]
+pub struct CostParams {
+    #[serde(default = "CostParams::default_base_cost")]
+    pub base_cost: u64,
+    #[serde(default = "CostParams::default_per_item_cost")]
+    pub per_item_cost: u64,
+}
+
+impl CostParams {
+    const fn default_base_cost() -> u64 {
+        10
+    }
+
+    const fn default_per_item_cost() -> u64 {
+        1
+    }
+}
+
+impl Default for CostParams {
+    fn default() -> Self {
+        Self {
+            base_cost: Self::default_base_cost(),
+            per_item_cost: Self::default_per_item_cost(),
+        }
+    }
+}
+
+/// A cost table keyed by [`RequestKind`]. Parsed leniently: a missing or unrecognized entry
+/// simply falls back to [`CostParams::default`], so that config files written against an older
+/// binary keep working once new request kinds are added.
+#[derive(Debug, Clone, Default)]
+pub struct CostTable(HashMap<RequestKind, CostParams>);
+
+impl CostTable {
+    pub fn get(&self, kind: RequestKind) -> CostParams {
+        self.0.get(&kind).copied().unwrap_or_default()
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for CostTable {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        // Unknown keys (e.g. a request kind introduced by a newer version of the binary reading
+        // an older config, or vice versa) are silently ignored rather than rejected.
+        let raw: HashMap<String, CostParams> = serde::Deserialize::deserialize(deserializer)?;
+        let table = raw
+            .into_iter()
+            .filter_map(|(key, params)| {
+                let kind: RequestKind =
+                    serde_json::from_value(serde_json::Value::String(key)).ok()?;
+                Some((kind, params))
+            })
+            .collect();
+        Ok(Self(table))
+    }
+}
+
+/// Configuration for [`FlowControl`].
+#[derive(Debug, Clone)]
+pub struct FlowControlConfig {
+    /// Maximum credit balance a peer can accumulate.
+    pub cap: u64,
+    /// Credits restored per second of elapsed time, up to `cap`.
+    pub recharge_per_sec: u64,
+    /// Upper bound on the number of items a single request is charged for, regardless of what it
+    /// actually asks for.
+    pub max_items: u64,
+    pub costs: CostTable,
+}
+
+impl Default for FlowControlConfig {
+    fn default() -> Self {
+        Self {
+            cap: 10_000,
+            recharge_per_sec: 100,
+            max_items: 1_000,
+            costs: CostTable::default(),
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("peer has insufficient flow-control credit to serve this request")]
+pub struct InsufficientCredit;
+
+#[derive(Debug)]
+struct PeerCredits {
+    balance: u64,
+    last_recharge: Instant,
+}
+
+impl PeerCredits {
+    fn new(cap: u64) -> Self {
+        Self {
+            balance: cap,
+            last_recharge: Instant::now(),
+        }
+    }
+
+    fn recharge(&mut self, config: &FlowControlConfig) {
+        let elapsed = self.last_recharge.elapsed();
+        let earned = (elapsed.as_secs_f64() * config.recharge_per_sec as f64) as u64;
+        if earned > 0 {
+            self.balance = self.balance.saturating_add(earned).min(config.cap);
+            self.last_recharge = Instant::now();
+        }
+    }
+}
+
+/// Tracks per-peer credit balances and decides whether an incoming request can be served.
+pub struct FlowControl {
+    config: FlowControlConfig,
+    peers: std::sync::Mutex<HashMap<libp2p_identity::PeerId, PeerCredits>>,
+}
+
+impl FlowControl {
+    pub fn new(config: FlowControlConfig) -> Self {
+        Self {
+            config,
+            peers: Default::default(),
+        }
+    }
+
+    /// The cost of serving a request for `iteration`, per the configured cost table for `kind`.
+    pub fn cost(&self, kind: RequestKind, iteration: &Iteration) -> u64 {
+        let params = self.config.costs.get(kind);
+        let items = iteration.limit.min(self.config.max_items);
+        params.base_cost + params.per_item_cost * items
+    }
+
+    /// Attempts to deduct the cost of serving `kind` for `iteration` from `peer`'s balance.
+    /// Peers are seen for the first time with a full balance. Returns
+    /// [`InsufficientCredit`] without deducting anything if the peer can't afford the request.
+    pub fn try_charge(
+        &self,
+        peer: libp2p_identity::PeerId,
+        kind: RequestKind,
+        iteration: &Iteration,
+    ) -> Result<(), InsufficientCredit> {
+        let cost = self.cost(kind, iteration);
+
+        let mut peers = self.peers.lock().unwrap();
+        let credits = peers
+            .entry(peer)
+            .or_insert_with(|| PeerCredits::new(self.config.cap));
+        credits.recharge(&self.config);
+
+        if credits.balance < cost {
+            return Err(InsufficientCredit);
+        }
+
+        credits.balance -= cost;
+        Ok(())
+    }
+
+    /// Forgets `peer`'s credit balance, so that the map does not grow without bound as peers
+    /// churn. If the peer reconnects it lands in the "first time, full balance" branch of
+    /// [`try_charge`](Self::try_charge) again.
+    pub fn on_peer_disconnected(&self, peer: libp2p_identity::PeerId) {
+        self.peers.lock().unwrap().remove(&peer);
+    }
+
+    /// Call from the swarm's `NetworkBehaviour::on_swarm_event` for every event, to evict a
+    /// peer's credit balance as soon as its last connection closes.
+    pub fn on_swarm_event(&self, event: &libp2p_swarm::FromSwarm) {
+        if let libp2p_swarm::FromSwarm::ConnectionClosed(closed) = event {
+            if closed.remaining_established == 0 {
+                self.on_peer_disconnected(closed.peer_id);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use p2p_proto::common::{Direction, Iteration};
+
+    use super::*;
+
+    fn iteration(limit: u64) -> Iteration {
+        Iteration {
+            start: Default::default(),
+            direction: Direction::Forward,
+            limit,
+            step: 1,
+        }
+    }
+
+    #[test]
+    fn refuses_when_balance_exhausted() {
+        let config = FlowControlConfig {
+            cap: 100,
+            recharge_per_sec: 0,
+            max_items: 1_000,
+            costs: CostTable::default(),
+        };
+        let flow_control = FlowControl::new(config);
+        let peer = libp2p_identity::PeerId::random();
+
+        assert!(flow_control
+            .try_charge(peer, RequestKind::Receipts, &iteration(50))
+            .is_ok());
+        // 10 (base) + 50 = 60 spent, 40 left, next request costs 10 + 50 = 60 -> refused.
+        assert!(flow_control
+            .try_charge(peer, RequestKind::Receipts, &iteration(50))
+            .is_err());
+    }
+
+    #[test]
+    fn disconnect_evicts_peer_balance() {
+        let config = FlowControlConfig {
+            cap: 100,
+            recharge_per_sec: 0,
+            max_items: 1_000,
+            costs: CostTable::default(),
+        };
+        let flow_control = FlowControl::new(config);
+        let peer = libp2p_identity::PeerId::random();
+
+        flow_control
+            .try_charge(peer, RequestKind::Receipts, &iteration(50))
+            .unwrap();
+        assert!(flow_control
+            .try_charge(peer, RequestKind::Receipts, &iteration(50))
+            .is_err());
+
+        // After a disconnect the peer is forgotten, so a reconnect starts over with a full
+        // balance rather than staying stuck at zero (and the map doesn't grow unbounded).
+        flow_control.on_peer_disconnected(peer);
+        assert!(flow_control
+            .try_charge(peer, RequestKind::Receipts, &iteration(50))
+            .is_ok());
+    }
+
+    #[test]
+    fn clamps_to_max_items() {
+        let config = FlowControlConfig {
+            cap: 1_000_000,
+            recharge_per_sec: 0,
+            max_items: 10,
+            costs: CostTable::default(),
+        };
+        let flow_control = FlowControl::new(config);
+
+        assert_eq!(
+            flow_control.cost(RequestKind::Receipts, &iteration(10)),
+            flow_control.cost(RequestKind::Receipts, &iteration(1_000_000)),
+        );
+    }
+}
@@ -0,0 +1,62 @@
+//! Serving side for [`ReceiptByHashRequest`](p2p_proto::receipt::ReceiptByHashRequest).
+use anyhow::Context;
+use p2p_proto::receipt::{ReceiptByHashRequest, ReceiptByHashResponse, ReceiptInclusionProof};
+use pathfinder_storage::Transaction;
+
+/// Returns [`ReceiptByHashResponse::Fin`] rather than an error if the transaction is unknown.
+pub fn serve_receipt_by_hash(
+    db_tx: &Transaction<'_>,
+    request: ReceiptByHashRequest,
+) -> anyhow::Result<ReceiptByHashResponse> {
+    let Some((_, receipt, block_hash)) = db_tx
+        .transaction_with_receipt(request.transaction_hash)
+        .context("Fetching receipt from database")?
+    else {
+        return Ok(ReceiptByHashResponse::Fin);
+    };
+
+    let proof = db_tx
+        .receipt_inclusion_proof(block_hash, request.transaction_hash)
+        .context("Computing receipt inclusion proof")?;
+
+    Ok(ReceiptByHashResponse::Receipt {
+        block_hash: block_hash.0.into(),
+        receipt: receipt.into(),
+        proof: proof.into(),
+    })
+}
+
+impl From<pathfinder_storage::ReceiptInclusionProof> for ReceiptInclusionProof {
+    fn from(proof: pathfinder_storage::ReceiptInclusionProof) -> Self {
+        Self {
+            index: proof.index,
+            siblings: proof.siblings.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pathfinder_crypto::Felt;
+
+    use super::*;
+
+    fn felt_from_u8(value: u8) -> Felt {
+        let mut bytes = [0u8; 32];
+        bytes[31] = value;
+        Felt::from_be_bytes(bytes).unwrap()
+    }
+
+    #[test]
+    fn inclusion_proof_conversion_preserves_index_and_siblings() {
+        let proof = pathfinder_storage::ReceiptInclusionProof {
+            index: 3,
+            siblings: vec![felt_from_u8(1), felt_from_u8(2)],
+        };
+
+        let converted: ReceiptInclusionProof = proof.into();
+
+        assert_eq!(converted.index, 3);
+        assert_eq!(converted.siblings.len(), 2);
+    }
+}
@@ -0,0 +1,35 @@
+//! Serving side for [`ReceiptsRequest`](p2p_proto::receipt::ReceiptsRequest).
+use anyhow::Context;
+use p2p_proto::receipt::{Receipt, ReceiptsRequest, ReceiptsResponse};
+use pathfinder_storage::Transaction;
+
+use super::flow_control::{FlowControl, RequestKind};
+
+/// Returns [`ReceiptsResponse::Fin`] without touching the database if `peer` doesn't have enough
+/// flow-control credit left to afford this request.
+pub fn serve_receipts(
+    db_tx: &Transaction<'_>,
+    flow_control: &FlowControl,
+    peer: libp2p_identity::PeerId,
+    request: ReceiptsRequest,
+) -> anyhow::Result<Vec<ReceiptsResponse>> {
+    if flow_control
+        .try_charge(peer, RequestKind::Receipts, &request.iteration)
+        .is_err()
+    {
+        tracing::debug!(%peer, "Refusing receipts request: insufficient flow-control credit");
+        return Ok(vec![ReceiptsResponse::Fin]);
+    }
+
+    let receipts = db_tx
+        .receipts_for_iteration(request.iteration)
+        .context("Fetching receipts from database")?;
+
+    let mut responses: Vec<_> = receipts
+        .into_iter()
+        .map(Receipt::from)
+        .map(ReceiptsResponse::Receipt)
+        .collect();
+    responses.push(ReceiptsResponse::Fin);
+    Ok(responses)
+}
@@ -13,6 +13,42 @@ pub struct MessageToL1 {
     pub to_address: EthereumAddress,
 }
 
+impl MessageToL1 {
+    /// The canonical L2->L1 message hash, as computed and checked by the Starknet core contract
+    /// on L1 via `keccak256(abi.encodePacked(fromAddress, toAddress, payload.length, payload))`.
+    /// `abi.encodePacked` packs `address` as its raw 20 bytes -- only the `uint256` fields
+    /// (`fromAddress`, the length, and each payload word) are padded to 32 bytes.
+    pub fn hash(&self) -> MessageToL1Hash {
+        use sha3::{Digest, Keccak256};
+
+        let mut hasher = Keccak256::new();
+        hasher.update(self.from_address.to_be_bytes());
+        hasher.update(self.to_address.0.as_bytes());
+        hasher.update(u256_be_bytes(self.payload.len() as u64));
+        for felt in &self.payload {
+            hasher.update(felt.to_be_bytes());
+        }
+
+        MessageToL1Hash(hasher.finalize().into())
+    }
+}
+
+/// 32 byte big-endian, left-padded with zeroes, matching the Solidity `uint256` encoding the L1
+/// core contract hashes against.
+fn pad_left_32(bytes: &[u8]) -> [u8; 32] {
+    let mut padded = [0u8; 32];
+    padded[32 - bytes.len()..].copy_from_slice(bytes);
+    padded
+}
+
+fn u256_be_bytes(value: u64) -> [u8; 32] {
+    pad_left_32(&value.to_be_bytes())
+}
+
+/// Keccak256 hash of a [`MessageToL1`], as consumed/produced on L1.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct MessageToL1Hash(pub [u8; 32]);
+
 // Avoid pathfinder_common dependency
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub struct EthereumAddress(pub H160);
@@ -108,6 +144,84 @@ pub enum ReceiptsResponse {
     Fin,
 }
 
+/// Requests a single transaction's receipt by hash. Parallel to [`ReceiptsRequest`], but
+/// targeted at one transaction instead of a range.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ToProtobuf, TryFromProtobuf, Dummy)]
+#[protobuf(name = "crate::proto::receipt::ReceiptByHashRequest")]
+pub struct ReceiptByHashRequest {
+    pub transaction_hash: Hash,
+}
+
+/// A receipt's index in its block plus the sibling hashes needed to recompute the block's
+/// receipt commitment root.
+#[derive(Debug, Clone, PartialEq, Eq, ToProtobuf, TryFromProtobuf, Dummy)]
+#[protobuf(name = "crate::proto::receipt::receipt_by_hash_response::InclusionProof")]
+pub struct ReceiptInclusionProof {
+    pub index: u64,
+    pub siblings: Vec<Hash>,
+}
+
+/// Parallel to [`ReceiptsResponse`], carrying the enclosing block hash and inclusion proof
+/// alongside the single requested receipt.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Dummy)]
+pub enum ReceiptByHashResponse {
+    Receipt {
+        block_hash: Hash,
+        receipt: Receipt,
+        proof: ReceiptInclusionProof,
+    },
+    #[default]
+    Fin,
+}
+
+impl ToProtobuf<proto::receipt::ReceiptByHashResponse> for ReceiptByHashResponse {
+    fn to_protobuf(self) -> proto::receipt::ReceiptByHashResponse {
+        use proto::receipt::receipt_by_hash_response::{ReceiptMessage, WithProof};
+
+        proto::receipt::ReceiptByHashResponse {
+            receipt_message: Some(match self {
+                Self::Receipt {
+                    block_hash,
+                    receipt,
+                    proof,
+                } => ReceiptMessage::Receipt(WithProof {
+                    block_hash: Some(block_hash.to_protobuf()),
+                    receipt: Some(receipt.to_protobuf()),
+                    proof: Some(proof.to_protobuf()),
+                }),
+                Self::Fin => ReceiptMessage::Fin(proto::common::Fin {}),
+            }),
+        }
+    }
+}
+
+impl TryFromProtobuf<proto::receipt::ReceiptByHashResponse> for ReceiptByHashResponse {
+    fn try_from_protobuf(
+        input: proto::receipt::ReceiptByHashResponse,
+        field_name: &'static str,
+    ) -> Result<Self, std::io::Error> {
+        use proto::receipt::receipt_by_hash_response::ReceiptMessage;
+
+        Ok(match proto_field(input.receipt_message, field_name)? {
+            ReceiptMessage::Receipt(with_proof) => Self::Receipt {
+                block_hash: TryFromProtobuf::try_from_protobuf(
+                    proto_field(with_proof.block_hash, field_name)?,
+                    field_name,
+                )?,
+                receipt: TryFromProtobuf::try_from_protobuf(
+                    proto_field(with_proof.receipt, field_name)?,
+                    field_name,
+                )?,
+                proof: TryFromProtobuf::try_from_protobuf(
+                    proto_field(with_proof.proof, field_name)?,
+                    field_name,
+                )?,
+            },
+            ReceiptMessage::Fin(_) => Self::Fin,
+        })
+    }
+}
+
 impl<T> Dummy<T> for EthereumAddress {
     fn dummy_with_rng<R: rand::Rng + ?Sized>(_: &T, rng: &mut R) -> Self {
         Self(H160::random_using(rng))
@@ -202,3 +316,36 @@ impl TryFromProtobuf<proto::receipt::ReceiptsResponse> for ReceiptsResponse {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn felt_from_u8(value: u8) -> Felt {
+        let mut bytes = [0u8; 32];
+        bytes[31] = value;
+        Felt::from_be_bytes(bytes).unwrap()
+    }
+
+    #[test]
+    fn message_to_l1_hash_matches_known_vector() {
+        // from_address = 1, to_address = 0x0102..0x14, payload = [2, 3]. Cross-checked with
+        // `openssl dgst -keccak-256` against the equivalent `abi.encodePacked` byte stream.
+        let message = MessageToL1 {
+            from_address: felt_from_u8(1),
+            payload: vec![felt_from_u8(2), felt_from_u8(3)],
+            to_address: EthereumAddress(H160::from_slice(
+                &(1..=20).collect::<Vec<u8>>(),
+            )),
+        };
+
+        assert_eq!(
+            message.hash().0,
+            [
+                0x62, 0x4f, 0x61, 0x39, 0x99, 0xee, 0xe8, 0x93, 0x55, 0xc2, 0xee, 0xbb, 0xa5,
+                0x81, 0xd7, 0xe4, 0x1f, 0x2e, 0xc2, 0xd6, 0x54, 0x08, 0xb1, 0x3e, 0x38, 0xe9,
+                0x50, 0x80, 0x4b, 0x2a, 0x65, 0xf8,
+            ]
+        );
+    }
+}
@@ -0,0 +1,113 @@
+use anyhow::Context;
+use pathfinder_common::{BlockId, Fee, TransactionHash};
+
+use super::get_transaction_status::{ExecutionStatus, FinalityStatus};
+use crate::context::RpcContext;
+
+#[derive(serde::Deserialize, Debug, PartialEq, Eq)]
+pub struct GetBlockReceiptsInput {
+    block_id: BlockId,
+}
+
+#[derive(Clone, serde::Serialize, Debug, PartialEq, Eq)]
+pub struct Receipt {
+    transaction_hash: TransactionHash,
+    actual_fee: Fee,
+    finality_status: FinalityStatus,
+    execution_status: ExecutionStatus,
+}
+
+#[derive(serde::Serialize, Debug, PartialEq, Eq)]
+pub struct GetBlockReceiptsOutput(Vec<Receipt>);
+
+crate::error::generate_rpc_error_subset!(GetBlockReceiptsError: BlockNotFound);
+
+/// Returns every receipt in the given block in a single round-trip, querying
+/// `block_is_l1_accepted` once and sharing the resulting [`FinalityStatus`] across all receipts.
+pub async fn get_block_receipts(
+    context: RpcContext,
+    input: GetBlockReceiptsInput,
+) -> Result<GetBlockReceiptsOutput, GetBlockReceiptsError> {
+    let span = tracing::Span::current();
+
+    let output = tokio::task::spawn_blocking(move || {
+        let _g = span.enter();
+
+        let mut db = context
+            .storage
+            .connection()
+            .context("Opening database connection")?;
+        let db_tx = db.transaction().context("Creating database transaction")?;
+
+        let Some((_, block_hash)) = db_tx.block_id(input.block_id).context("Fetching block")?
+        else {
+            return anyhow::Ok(None);
+        };
+
+        let Some(receipts) = db_tx
+            .transactions_with_receipts_for_block(input.block_id)
+            .context("Fetching receipts from database")?
+        else {
+            return anyhow::Ok(None);
+        };
+
+        let l1_accepted = db_tx
+            .block_is_l1_accepted(block_hash.into())
+            .context("Querying block's status")?;
+        let finality_status = if l1_accepted {
+            FinalityStatus::AcceptedOnL1
+        } else {
+            FinalityStatus::AcceptedOnL2
+        };
+
+        let receipts = receipts
+            .into_iter()
+            .map(|(transaction, receipt)| Receipt {
+                transaction_hash: transaction.hash,
+                actual_fee: receipt.actual_fee,
+                finality_status,
+                execution_status: receipt.execution_status.into(),
+            })
+            .collect();
+
+        Ok(Some(GetBlockReceiptsOutput(receipts)))
+    })
+    .await
+    .context("Joining database task")??;
+
+    output.ok_or(GetBlockReceiptsError::BlockNotFound)
+}
+
+#[cfg(test)]
+mod tests {
+    use pathfinder_common::BlockNumber;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn block_not_found() {
+        let context = RpcContext::for_tests();
+        let input = GetBlockReceiptsInput {
+            block_id: BlockId::Number(BlockNumber::new_or_panic(9999)),
+        };
+
+        let error = get_block_receipts(context, input).await.unwrap_err();
+        assert!(matches!(error, GetBlockReceiptsError::BlockNotFound));
+    }
+
+    #[tokio::test]
+    async fn shares_one_finality_status_across_all_receipts_in_the_block() {
+        let context = RpcContext::for_tests();
+        // Block 0 is L1 accepted and contains more than one transaction in the test fixture.
+        let input = GetBlockReceiptsInput {
+            block_id: BlockId::Number(BlockNumber::GENESIS),
+        };
+
+        let output = get_block_receipts(context, input).await.unwrap();
+        assert!(output.0.len() > 1);
+        assert!(output
+            .0
+            .iter()
+            .all(|receipt| receipt.finality_status == FinalityStatus::AcceptedOnL1));
+    }
+}
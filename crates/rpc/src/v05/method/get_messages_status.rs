@@ -0,0 +1,126 @@
+use anyhow::Context;
+use p2p_proto::receipt::{EthereumAddress, MessageToL1};
+use pathfinder_common::TransactionHash;
+use primitive_types::H256;
+use serde_with::skip_serializing_none;
+
+use super::get_transaction_status;
+use super::get_transaction_status::{ExecutionStatus, FinalityStatus};
+use crate::context::RpcContext;
+
+#[derive(serde::Deserialize, Debug, PartialEq, Eq)]
+pub struct GetMessagesStatusInput {
+    /// The hash of the L1 transaction that sent the `L1_HANDLER` transactions being looked up.
+    transaction_hash: H256,
+}
+
+#[derive(serde::Serialize, Debug, PartialEq, Eq)]
+#[skip_serializing_none]
+pub struct MessageStatus {
+    transaction_hash: TransactionHash,
+    finality_status: FinalityStatus,
+    /// Not present for received or rejected transactions.
+    execution_status: Option<ExecutionStatus>,
+    /// The L2->L1 message hashes computed from this transaction's own `messages_sent`, so a
+    /// caller can correlate them against the L1 core contract's consumed-message bookkeeping.
+    l2_to_l1_message_hashes: Vec<String>,
+}
+
+#[derive(serde::Serialize, Debug, PartialEq, Eq)]
+pub struct GetMessagesStatusOutput(Vec<MessageStatus>);
+
+crate::error::generate_rpc_error_subset!(GetMessagesStatusError: TxnHashNotFoundV04);
+
+/// Given an L1 transaction hash, returns every `L1_HANDLER` transaction it triggered together
+/// with its current status.
+pub async fn get_messages_status(
+    context: RpcContext,
+    input: GetMessagesStatusInput,
+) -> Result<GetMessagesStatusOutput, GetMessagesStatusError> {
+    let span = tracing::Span::current();
+
+    let l1_handler_transactions = tokio::task::spawn_blocking({
+        let context = context.clone();
+        move || {
+            let _g = span.enter();
+
+            let mut db = context
+                .storage
+                .connection()
+                .context("Opening database connection")?;
+            let db_tx = db.transaction().context("Creating database transaction")?;
+
+            let transaction_hashes = db_tx
+                .l1_handler_transaction_hashes_for_l1_transaction(input.transaction_hash)
+                .context("Fetching L1 handler transactions triggered by this L1 transaction")?;
+
+            transaction_hashes
+                .into_iter()
+                .map(|transaction_hash| {
+                    let messages_sent = db_tx
+                        .transaction_with_receipt(transaction_hash)
+                        .context("Fetching receipt from database")?
+                        .map(|(_, receipt, _)| receipt.l2_to_l1_messages)
+                        .unwrap_or_default();
+                    Ok((transaction_hash, messages_sent))
+                })
+                .collect::<anyhow::Result<Vec<_>>>()
+        }
+    })
+    .await
+    .context("Joining database task")??;
+
+    if l1_handler_transactions.is_empty() {
+        return Err(GetMessagesStatusError::TxnHashNotFoundV04);
+    }
+
+    let mut statuses = Vec::with_capacity(l1_handler_transactions.len());
+    for (transaction_hash, messages_sent) in l1_handler_transactions {
+        let status = get_transaction_status::lookup(context.clone(), transaction_hash)
+            .await
+            .map_err(|_| GetMessagesStatusError::TxnHashNotFoundV04)?;
+
+        let l2_to_l1_message_hashes = messages_sent
+            .into_iter()
+            .map(|message| l2_to_l1_message_hash(&message))
+            .collect();
+
+        statuses.push(MessageStatus {
+            transaction_hash,
+            finality_status: status.finality_status,
+            execution_status: status.execution_status,
+            l2_to_l1_message_hashes,
+        });
+    }
+
+    Ok(GetMessagesStatusOutput(statuses))
+}
+
+fn l2_to_l1_message_hash(
+    message: &starknet_gateway_types::reply::transaction::L2ToL1Message,
+) -> String {
+    let message = MessageToL1 {
+        from_address: message.from_address.0,
+        payload: message.payload.iter().map(|felt| felt.0).collect(),
+        to_address: EthereumAddress(message.to_address.0),
+    };
+    format!("0x{}", hex::encode(message.hash().0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn not_found() {
+        // No L1 transaction in the test fixture triggered any L1_HANDLER transaction with this
+        // hash.
+        let context = RpcContext::for_tests();
+        let input = GetMessagesStatusInput {
+            transaction_hash: H256::zero(),
+        };
+
+        let error = get_messages_status(context, input).await.unwrap_err();
+        assert!(matches!(error, GetMessagesStatusError::TxnHashNotFoundV04));
+    }
+}
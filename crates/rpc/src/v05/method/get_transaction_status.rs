@@ -10,12 +10,12 @@ pub struct GetTransactionStatusInput {
     transaction_hash: TransactionHash,
 }
 
-#[derive(serde::Serialize, Debug, PartialEq, Eq)]
+#[derive(Clone, serde::Serialize, Debug, PartialEq, Eq)]
 #[skip_serializing_none]
 pub struct GetTransactionStatusOutput {
-    finality_status: FinalityStatus,
+    pub(crate) finality_status: FinalityStatus,
     /// Not present for received or rejected transactions.
-    execution_status: Option<ExecutionStatus>,
+    pub(crate) execution_status: Option<ExecutionStatus>,
 }
 
 #[derive(Copy, Clone, Debug, serde::Serialize, PartialEq, Eq)]
@@ -71,10 +71,19 @@ crate::error::generate_rpc_error_subset!(GetTransactionStatusError: TxnHashNotFo
 pub async fn get_transaction_status(
     context: RpcContext,
     input: GetTransactionStatusInput,
+) -> Result<GetTransactionStatusOutput, GetTransactionStatusError> {
+    lookup(context, input.transaction_hash).await
+}
+
+/// The three-tier lookup shared by [`get_transaction_status`] and
+/// `subscribe_transaction_status`: pending block, then database, then gateway.
+pub(crate) async fn lookup(
+    context: RpcContext,
+    transaction_hash: TransactionHash,
 ) -> Result<GetTransactionStatusOutput, GetTransactionStatusError> {
     // Check in pending block.
     if let Some(pending) = &context.pending_data {
-        if let Some(status) = pending_status(pending, &input.transaction_hash).await {
+        if let Some(status) = pending_status(pending, &transaction_hash).await {
             return Ok(status);
         }
     }
@@ -92,7 +101,7 @@ pub async fn get_transaction_status(
         let db_tx = db.transaction().context("Creating database transaction")?;
 
         let Some((_, receipt, block_hash)) = db_tx
-            .transaction_with_receipt(input.transaction_hash)
+            .transaction_with_receipt(transaction_hash)
             .context("Fetching receipt from database")?
         else {
             return anyhow::Ok(None);
@@ -124,7 +133,7 @@ pub async fn get_transaction_status(
     use starknet_gateway_client::GatewayApi;
     context
         .sequencer
-        .transaction(input.transaction_hash)
+        .transaction(transaction_hash)
         .await
         .context("Fetching transaction from gateway")
         .and_then(|tx| {
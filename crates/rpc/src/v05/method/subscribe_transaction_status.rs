@@ -0,0 +1,130 @@
+use futures::Stream;
+use pathfinder_common::TransactionHash;
+
+use super::get_transaction_status::{
+    self, FinalityStatus, GetTransactionStatusError, GetTransactionStatusOutput,
+};
+use crate::context::RpcContext;
+
+#[derive(serde::Deserialize, Debug, PartialEq, Eq)]
+pub struct SubscribeTransactionStatusInput {
+    transaction_hash: TransactionHash,
+}
+
+/// Emitted every time the observed status of the subscribed transaction changes.
+pub type SubscribeTransactionStatusOutput = GetTransactionStatusOutput;
+
+/// A notification that the node has observed a new pending or accepted block.
+#[derive(Debug, Clone, Copy)]
+pub struct BlockUpdate;
+
+/// Re-checks the transaction's status on every `block_updates` tick, emitting it whenever it
+/// changes and ending the stream once a terminal status is reached. A lookup that does not find
+/// the transaction (not observed yet) is treated like an unchanged status; any other lookup error
+/// is yielded and ends the stream.
+pub fn subscribe_transaction_status(
+    context: RpcContext,
+    input: SubscribeTransactionStatusInput,
+    mut block_updates: tokio::sync::broadcast::Receiver<BlockUpdate>,
+) -> impl Stream<Item = Result<SubscribeTransactionStatusOutput, GetTransactionStatusError>> {
+    async_stream::try_stream! {
+        let mut last_sent: Option<SubscribeTransactionStatusOutput> = None;
+
+        loop {
+            match get_transaction_status::lookup(context.clone(), input.transaction_hash).await {
+                Ok(status) if last_sent.as_ref() != Some(&status) => {
+                    let reached_terminal_state = is_terminal(&status);
+                    last_sent = Some(status.clone());
+                    yield status;
+
+                    if reached_terminal_state {
+                        return;
+                    }
+                }
+                // Unchanged status: nothing to emit.
+                Ok(_) => {}
+                // Not observed yet: keep waiting for the next block update.
+                Err(GetTransactionStatusError::TxnHashNotFoundV04) => {}
+                // A genuine failure (DB down, join error, ...): log it and end the stream rather
+                // than polling forever in silence.
+                Err(error) => {
+                    tracing::warn!(%error, "Transaction status subscription lookup failed");
+                    Err(error)?;
+                }
+            }
+
+            loop {
+                match block_updates.recv().await {
+                    Ok(_) => break,
+                    // The node is shutting down: no more updates will ever arrive.
+                    Err(tokio::sync::broadcast::error::RecvError::Closed) => return,
+                    // We fell behind the broadcast buffer during a burst of blocks; the node is
+                    // still healthy, so just re-check status on the next tick.
+                    Err(tokio::sync::broadcast::error::RecvError::Lagged(skipped)) => {
+                        tracing::debug!(skipped, "transaction status subscription lagged behind block updates");
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn is_terminal(status: &GetTransactionStatusOutput) -> bool {
+    matches!(
+        status.finality_status,
+        FinalityStatus::AcceptedOnL1 | FinalityStatus::Rejected
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::StreamExt;
+    use pathfinder_common::macro_prelude::*;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn stops_at_terminal_state() {
+        let context = RpcContext::for_tests();
+        // This transaction is in block 0 which is L1 accepted, so the very first lookup already
+        // observes a terminal state and the stream should end without waiting for a notification.
+        let (_tx, rx) = tokio::sync::broadcast::channel(1);
+        let input = SubscribeTransactionStatusInput {
+            transaction_hash: transaction_hash_bytes!(b"txn 0"),
+        };
+
+        let statuses: Vec<_> = subscribe_transaction_status(context, input, rx)
+            .collect()
+            .await;
+
+        assert_eq!(statuses.len(), 1);
+        assert_eq!(
+            statuses[0].as_ref().unwrap().finality_status,
+            FinalityStatus::AcceptedOnL1
+        );
+    }
+
+    #[tokio::test]
+    async fn survives_lookup_errors_instead_of_ending_the_stream() {
+        let context = RpcContext::for_tests();
+        // Not a real transaction: every lookup errors with "not found". The subscription should
+        // keep waiting for updates instead of ending the stream on the first error, and only stop
+        // once the channel is actually closed.
+        let (tx, rx) = tokio::sync::broadcast::channel(4);
+        let input = SubscribeTransactionStatusInput {
+            transaction_hash: transaction_hash_bytes!(b"does not exist"),
+        };
+
+        tx.send(BlockUpdate).unwrap();
+        tx.send(BlockUpdate).unwrap();
+        drop(tx);
+
+        let statuses: Vec<_> = subscribe_transaction_status(context, input, rx)
+            .collect()
+            .await;
+
+        // No error is ever yielded to the subscriber, and the stream ends cleanly once the
+        // sender (and thus the node's block-update channel) is gone.
+        assert!(statuses.is_empty());
+    }
+}